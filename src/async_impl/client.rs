@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+#[cfg(feature = "trust-dns")]
+use std::net::IpAddr;
+
+#[cfg(feature = "trust-dns")]
+use crate::dns::trust_dns::{
+    IpVersionPreference, ResolverConfig, ResolverOpts, SrvRecord, TrustDnsResolver,
+};
+use crate::error::BoxError;
+
+/// An asynchronous `Client` to make Requests with.
+///
+/// The `Client` holds a connection pool internally, so it is advised that
+/// you create one and **reuse** it.
+#[derive(Clone)]
+pub struct Client {
+    #[cfg(feature = "trust-dns")]
+    dns_resolver: Arc<TrustDnsResolver>,
+}
+
+impl Client {
+    /// Look up the TXT records for `name` using the client's configured
+    /// DNS resolver.
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub async fn dns_lookup_txt(&self, name: &str) -> crate::Result<Vec<String>> {
+        self.dns_resolver
+            .lookup_txt(name)
+            .await
+            .map_err(crate::error::request)
+    }
+
+    /// Look up the SRV records for `name` (e.g. `_service._proto.host`)
+    /// using the client's configured DNS resolver.
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub async fn dns_lookup_srv(&self, name: &str) -> crate::Result<Vec<SrvRecord>> {
+        self.dns_resolver
+            .lookup_srv(name)
+            .await
+            .map_err(crate::error::request)
+    }
+}
+
+/// A `ClientBuilder` can be used to create a `Client` with custom
+/// configuration.
+#[derive(Default)]
+pub struct ClientBuilder {
+    #[cfg(feature = "trust-dns")]
+    dns_resolver: Option<TrustDnsResolver>,
+    #[cfg(feature = "trust-dns")]
+    dns_ip_version_preference: Option<IpVersionPreference>,
+    #[cfg(feature = "trust-dns")]
+    dns_srv_service: Option<(String, String)>,
+    #[cfg(feature = "trust-dns")]
+    shared_dns_resolver: bool,
+}
+
+impl ClientBuilder {
+    /// Constructs a new `ClientBuilder`.
+    pub fn new() -> Self {
+        ClientBuilder::default()
+    }
+
+    /// Use an explicit `ResolverConfig` and `ResolverOpts` for DNS
+    /// resolution instead of reading `/etc/resolv.conf`.
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub fn dns_resolver_config(mut self, config: ResolverConfig, opts: ResolverOpts) -> Self {
+        self.dns_resolver = Some(TrustDnsResolver::with_config(config, opts));
+        self
+    }
+
+    /// Resolve DNS over TLS, using the given nameservers paired with the
+    /// TLS name each should be validated against.
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub fn dns_over_tls(mut self, nameservers: &[(IpAddr, String)]) -> Self {
+        self.dns_resolver = Some(TrustDnsResolver::with_dns_over_tls(nameservers));
+        self
+    }
+
+    /// Resolve DNS over HTTPS, using the given nameservers paired with
+    /// the TLS name each should be validated against.
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub fn dns_over_https(mut self, nameservers: &[(IpAddr, String)]) -> Self {
+        self.dns_resolver = Some(TrustDnsResolver::with_dns_over_https(nameservers));
+        self
+    }
+
+    /// Override the IP version preference used to order the addresses
+    /// returned from DNS lookups, regardless of which resolver is in use.
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub fn dns_ip_version_preference(mut self, ip_version: IpVersionPreference) -> Self {
+        self.dns_ip_version_preference = Some(ip_version);
+        self
+    }
+
+    /// Resolve requests by discovering their target via an SRV lookup for
+    /// `_{service}._{proto}.{host}` instead of a plain `A`/`AAAA` lookup,
+    /// preserving the SRV targets' real ports.
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub fn dns_srv_service(mut self, service: &str, proto: &str) -> Self {
+        self.dns_srv_service = Some((service.to_owned(), proto.to_owned()));
+        self
+    }
+
+    /// Share the DNS resolver's `AsyncResolver` and cache across every
+    /// `Client` that also opts in, instead of building a fresh one per
+    /// `Client`. This avoids building a new `AsyncResolver` and re-warming
+    /// the DNS cache when an application spins up many short-lived clients.
+    ///
+    /// Can't be combined with [`dns_resolver_config`], [`dns_over_tls`], or
+    /// [`dns_over_https`]: the shared resolver always uses the system
+    /// configuration, so `build()` returns an `Err` if both are set.
+    ///
+    /// [`dns_resolver_config`]: ClientBuilder::dns_resolver_config
+    /// [`dns_over_tls`]: ClientBuilder::dns_over_tls
+    /// [`dns_over_https`]: ClientBuilder::dns_over_https
+    ///
+    /// This requires the `trust-dns` feature to be enabled.
+    #[cfg(feature = "trust-dns")]
+    pub fn shared_dns_resolver(mut self, shared: bool) -> Self {
+        self.shared_dns_resolver = shared;
+        self
+    }
+
+    /// Returns a `Client` that uses this `ClientBuilder` configuration.
+    pub fn build(self) -> Result<Client, BoxError> {
+        #[cfg(feature = "trust-dns")]
+        let dns_resolver = match self.dns_resolver {
+            Some(resolver) => resolver,
+            None => TrustDnsResolver::new()?,
+        };
+        #[cfg(feature = "trust-dns")]
+        if self.shared_dns_resolver && dns_resolver.has_explicit_config() {
+            return Err(BoxError::from(
+                "shared_dns_resolver can't be combined with an explicit DNS resolver config",
+            ));
+        }
+        #[cfg(feature = "trust-dns")]
+        let dns_resolver = if self.shared_dns_resolver {
+            dns_resolver.with_shared_dns_resolver()
+        } else {
+            dns_resolver
+        };
+        #[cfg(feature = "trust-dns")]
+        let dns_resolver = match self.dns_ip_version_preference {
+            Some(ip_version) => dns_resolver.with_ip_version_preference(ip_version),
+            None => dns_resolver,
+        };
+        #[cfg(feature = "trust-dns")]
+        let dns_resolver = match self.dns_srv_service {
+            Some((service, proto)) => dns_resolver.with_srv_service(&service, &proto),
+            None => dns_resolver,
+        };
+
+        Ok(Client {
+            #[cfg(feature = "trust-dns")]
+            dns_resolver: Arc::new(dns_resolver),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "trust-dns"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_errs_when_shared_resolver_combined_with_explicit_config() {
+        let result = ClientBuilder::new()
+            .dns_resolver_config(ResolverConfig::default(), ResolverOpts::default())
+            .shared_dns_resolver(true)
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_succeeds_with_shared_resolver_alone() {
+        let result = ClientBuilder::new().shared_dns_resolver(true).build();
+        assert!(result.is_ok());
+    }
+}
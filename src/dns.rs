@@ -0,0 +1,21 @@
+//! DNS resolution
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+
+use hyper::client::connect::dns::Name;
+
+use crate::error::BoxError;
+
+pub(crate) type Addrs = Box<dyn Iterator<Item = SocketAddr> + Send>;
+
+pub(crate) type Resolving = Pin<Box<dyn Future<Output = Result<Addrs, BoxError>> + Send>>;
+
+/// A plugin that can resolve host names.
+pub(crate) trait Resolve: Send + Sync {
+    fn resolve(&self, name: Name) -> Resolving;
+}
+
+#[cfg(feature = "trust-dns")]
+pub mod trust_dns;
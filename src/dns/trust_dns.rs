@@ -3,14 +3,18 @@
 use hyper::client::connect::dns::Name;
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
+// Re-exported (via the now-public `trust_dns` module) so that callers of
+// `ClientBuilder::dns_resolver_config` can name these types without adding
+// their own `trust-dns-resolver` dependency.
 pub use trust_dns_resolver::config::{ResolverConfig, ResolverOpts};
+use trust_dns_resolver::config::{LookupIpStrategy, NameServerConfigGroup};
+use trust_dns_resolver::proto::rr::rdata::SRV;
 use trust_dns_resolver::{
-    lookup_ip::LookupIpIntoIter, system_conf, AsyncResolver, TokioConnection,
-    TokioConnectionProvider, TokioHandle,
+    system_conf, AsyncResolver, TokioConnection, TokioConnectionProvider, TokioHandle,
 };
 
 use std::io;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
 use super::{Addrs, Resolve, Resolving};
@@ -19,10 +23,133 @@ use crate::error::BoxError;
 
 type SharedResolver = Arc<AsyncResolver<TokioConnection, TokioConnectionProvider>>;
 
+/// Which IP address families to prefer when ordering the addresses
+/// returned from a lookup.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest::dns::trust_dns::IpVersionPreference;
+///
+/// let builder = reqwest::ClientBuilder::new()
+///     .dns_ip_version_preference(IpVersionPreference::Happy);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVersionPreference {
+    /// Don't reorder, return addresses in the order the resolver gave them.
+    System,
+    /// Put IPv4 addresses first, but still return IPv6 ones after them.
+    Ipv4First,
+    /// Put IPv6 addresses first, but still return IPv4 ones after them.
+    Ipv6First,
+    /// Interleave IPv4 and IPv6 addresses, alternating families per RFC
+    /// 6555/8305 Happy Eyeballs address ordering, so a connector racing
+    /// both families gets a candidate of each as early as possible.
+    Happy,
+}
+
+impl Default for IpVersionPreference {
+    fn default() -> Self {
+        IpVersionPreference::System
+    }
+}
+
+impl From<LookupIpStrategy> for IpVersionPreference {
+    fn from(strategy: LookupIpStrategy) -> Self {
+        match strategy {
+            LookupIpStrategy::Ipv4Only => IpVersionPreference::Ipv4First,
+            LookupIpStrategy::Ipv6Only => IpVersionPreference::Ipv6First,
+            LookupIpStrategy::Ipv4AndIpv6
+            | LookupIpStrategy::Ipv6thenIpv4
+            | LookupIpStrategy::Ipv4thenIpv6 => IpVersionPreference::System,
+        }
+    }
+}
+
+/// Order `addrs` according to `preference`, without ever dropping addresses
+/// of either family.
+fn order_by_ip_version(mut addrs: Vec<IpAddr>, preference: IpVersionPreference) -> Vec<IpAddr> {
+    match preference {
+        IpVersionPreference::System => addrs,
+        IpVersionPreference::Ipv4First => {
+            addrs.sort_by_key(|addr| !addr.is_ipv4());
+            addrs
+        }
+        IpVersionPreference::Ipv6First => {
+            addrs.sort_by_key(|addr| !addr.is_ipv6());
+            addrs
+        }
+        IpVersionPreference::Happy => interleave_by_ip_version(addrs),
+    }
+}
+
+/// Interleave `addrs` into alternating IPv6/IPv4 order (one AAAA, one A,
+/// and so on), per RFC 6555/8305 Happy Eyeballs address ordering, so a
+/// connector racing both families gets a candidate of each as early as
+/// possible. Each family keeps its relative order; once one family is
+/// exhausted, the remainder of the other is appended unchanged.
+fn interleave_by_ip_version(addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+    let (v6, v4): (Vec<IpAddr>, Vec<IpAddr>) = addrs.into_iter().partition(|addr| addr.is_ipv6());
+
+    let mut v6 = v6.into_iter();
+    let mut v4 = v4.into_iter();
+    let mut ordered = Vec::with_capacity(v6.len() + v4.len());
+    loop {
+        let next_v6 = v6.next();
+        let next_v4 = v4.next();
+        if next_v6.is_none() && next_v4.is_none() {
+            break;
+        }
+        ordered.extend(next_v6);
+        ordered.extend(next_v4);
+    }
+    ordered
+}
+
+/// Order SRV records by priority (ascending), breaking ties within a
+/// priority group via weighted-random selection, per RFC 2782.
+fn order_srv_records(mut records: Vec<SrvRecord>) -> Vec<SrvRecord> {
+    records.sort_by_key(|record| record.priority);
+
+    let mut ordered = Vec::with_capacity(records.len());
+    while !records.is_empty() {
+        let priority = records[0].priority;
+        let split = records
+            .iter()
+            .position(|record| record.priority != priority)
+            .unwrap_or(records.len());
+        let group = records.drain(..split).collect();
+        ordered.extend(weighted_shuffle(group));
+    }
+    ordered
+}
+
+/// Repeatedly pick a record at random, weighted by its `weight` (plus one,
+/// so a weight of `0` can still be picked per RFC 2782), removing it from
+/// consideration until the whole group has been drained.
+fn weighted_shuffle(mut group: Vec<SrvRecord>) -> Vec<SrvRecord> {
+    let mut ordered = Vec::with_capacity(group.len());
+    while !group.is_empty() {
+        let total: u32 = group.iter().map(|record| record.weight as u32 + 1).sum();
+        let mut pick = fastrand::u32(0..total);
+        let mut chosen = 0;
+        for (i, record) in group.iter().enumerate() {
+            let weight = record.weight as u32 + 1;
+            if pick < weight {
+                chosen = i;
+                break;
+            }
+            pick -= weight;
+        }
+        ordered.push(group.remove(chosen));
+    }
+    ordered
+}
+
 lazy_static! {
-    static ref SYSTEM_CONF: Mutex<Lazy<io::Result<(ResolverConfig, ResolverOpts)>>> = {
+    static ref SYSTEM_CONF: std::sync::Mutex<Lazy<io::Result<(ResolverConfig, ResolverOpts)>>> = {
         let data = Lazy::new(|| system_conf::read_system_conf().map_err(io::Error::from));
-        Mutex::new(data)
+        std::sync::Mutex::new(data)
     };
 }
 
@@ -32,26 +159,81 @@ pub fn reinitialize_system_conf() {
 }
 
 fn get_system_conf() -> io::Result<(ResolverConfig, ResolverOpts)> {
-    let mut conf = SYSTEM_CONF.lock().unwrap();
-    if conf.is_none() {
-        *conf = Some(initialize_system_conf());
+    let conf = SYSTEM_CONF.lock().unwrap();
+    match conf.as_ref() {
+        Ok((config, opts)) => Ok((config.clone(), opts.clone())),
+        Err(e) => Err(io::Error::new(e.kind(), e.to_string())),
     }
-    conf.clone().unwrap()
+}
+
+/// A single `AsyncResolver` shared by every `TrustDnsResolver` that opts in
+/// via `with_shared_dns_resolver`, so repeated `Client` construction doesn't
+/// build a new `AsyncResolver` or re-warm the DNS cache.
+static GLOBAL_RESOLVER: Lazy<Mutex<Option<SharedResolver>>> = Lazy::new(|| Mutex::new(None));
+
+async fn global_resolver() -> Result<SharedResolver, BoxError> {
+    let mut guard = GLOBAL_RESOLVER.lock().await;
+    if let Some(resolver) = &*guard {
+        return Ok(resolver.clone());
+    }
+
+    let (config, opts) =
+        get_system_conf().map_err(|e| format!("error reading DNS system conf: {}", e))?;
+    let resolver = new_resolver_with_config(config, opts)?;
+    *guard = Some(resolver.clone());
+    Ok(resolver)
 }
 
 /// Wrapper around an `AsyncResolver`, which implements the `Resolve` trait.
 #[derive(Debug, Clone)]
 pub(crate) struct TrustDnsResolver {
     state: Arc<Mutex<State>>,
+    ip_version: IpVersionPreference,
+    srv: Option<String>,
+    shared: bool,
+    explicit_config: bool,
 }
 
 struct SocketAddrs {
-    iter: LookupIpIntoIter,
+    iter: std::vec::IntoIter<SocketAddr>,
+}
+
+/// A single SRV record, as returned by [`TrustDnsResolver::lookup_srv`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvRecord {
+    /// The priority of this target host, lower values are more preferred.
+    pub priority: u16,
+    /// A server selection weight for records with the same priority.
+    pub weight: u16,
+    /// The port on the target host for this service.
+    pub port: u16,
+    /// The domain name of the target host.
+    pub target: String,
+}
+
+impl From<&SRV> for SrvRecord {
+    fn from(srv: &SRV) -> Self {
+        SrvRecord {
+            priority: srv.priority(),
+            weight: srv.weight(),
+            port: srv.port(),
+            target: srv.target().to_string(),
+        }
+    }
+}
+
+/// Ensure `name` ends with a trailing `.`, as trust-dns expects for lookups.
+fn fully_qualify(name: &str) -> String {
+    if name.ends_with('.') {
+        name.to_owned()
+    } else {
+        format!("{}.", name)
+    }
 }
 
 #[derive(Debug)]
 enum State {
-    Init,
+    Init(ResolverConfig, ResolverOpts),
     Ready(SharedResolver),
 }
 
@@ -59,41 +241,196 @@ impl TrustDnsResolver {
     /// Create a new resolver with the default configuration,
     /// which reads from `/etc/resolve.conf`.
     pub fn new() -> io::Result<Self> {
-        get_system_conf().as_ref().map_err(|e| {
+        let (config, opts) = get_system_conf().map_err(|e| {
             io::Error::new(e.kind(), format!("error reading DNS system conf: {}", e))
         })?;
+        let ip_version = IpVersionPreference::from(opts.ip_strategy);
 
         // At this stage, we might not have been called in the context of a
         // Tokio Runtime, so we must delay the actual construction of the
         // resolver.
         Ok(TrustDnsResolver {
-            state: Arc::new(Mutex::new(State::Init)),
+            state: Arc::new(Mutex::new(State::Init(config, opts))),
+            ip_version,
+            srv: None,
+            shared: false,
+            explicit_config: false,
         })
     }
+
+    /// Create a new resolver using an explicit `ResolverConfig` and
+    /// `ResolverOpts`, bypassing the system configuration entirely.
+    ///
+    /// This lets callers point the resolver at specific upstream
+    /// nameservers and tune options like `attempts`, `timeout`,
+    /// `cache_size`, or `ip_strategy` without touching `/etc/resolv.conf`.
+    pub fn with_config(config: ResolverConfig, opts: ResolverOpts) -> Self {
+        let ip_version = IpVersionPreference::from(opts.ip_strategy);
+        TrustDnsResolver {
+            state: Arc::new(Mutex::new(State::Init(config, opts))),
+            ip_version,
+            srv: None,
+            shared: false,
+            explicit_config: true,
+        }
+    }
+
+    /// Override the IP version preference used to order addresses returned
+    /// from a lookup, regardless of what `ResolverOpts::ip_strategy` says.
+    pub fn with_ip_version_preference(mut self, ip_version: IpVersionPreference) -> Self {
+        self.ip_version = ip_version;
+        self
+    }
+
+    /// Resolve hosts via an SRV lookup for `_{service}._{proto}.{host}`
+    /// instead of a plain `A`/`AAAA` lookup, returning the resolved SRV
+    /// targets' `SocketAddr`s with their real ports preserved, ordered by
+    /// SRV priority (ascending) then weighted-random within a priority
+    /// group, as described in RFC 2782.
+    pub fn with_srv_service(mut self, service: &str, proto: &str) -> Self {
+        self.srv = Some(format!("_{}._{}", service, proto));
+        self
+    }
+
+    /// Share this resolver's `AsyncResolver` with every other
+    /// `TrustDnsResolver` that also opts in, instead of building one per
+    /// instance. This avoids building a new `AsyncResolver` and re-warming
+    /// the DNS cache when an application spins up many short-lived clients.
+    ///
+    /// Can't be combined with an explicit `ResolverConfig`/`ResolverOpts`
+    /// (`with_config`, `with_dns_over_tls`, `with_dns_over_https`); the
+    /// shared resolver always uses the system configuration. Combining
+    /// both is caught by `ClientBuilder::build`, which returns an `Err`
+    /// instead of constructing the `Client`.
+    pub(crate) fn with_shared_dns_resolver(mut self) -> Self {
+        self.shared = true;
+        self
+    }
+
+    /// Whether this resolver was built from an explicit `ResolverConfig`
+    /// rather than the system configuration.
+    pub(crate) fn has_explicit_config(&self) -> bool {
+        self.explicit_config
+    }
+
+    /// Get the lazily-constructed `AsyncResolver`, building it on first use.
+    async fn shared_resolver(&self) -> Result<SharedResolver, BoxError> {
+        if self.shared {
+            return global_resolver().await;
+        }
+
+        let mut lock = self.state.lock().await;
+
+        let resolver = match &*lock {
+            State::Init(config, opts) => {
+                let resolver = new_resolver_with_config(config.clone(), opts.clone())?;
+                *lock = State::Ready(resolver.clone());
+                resolver
+            }
+            State::Ready(resolver) => resolver.clone(),
+        };
+
+        // Don't keep lock once the resolver is constructed, otherwise
+        // only one lookup could be done at a time.
+        drop(lock);
+
+        Ok(resolver)
+    }
+
+    /// Look up the TXT records for `name`, returning each record's
+    /// character-strings joined together.
+    pub async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, BoxError> {
+        let resolver = self.shared_resolver().await?;
+        let lookup = resolver.txt_lookup(fully_qualify(name)).await?;
+        Ok(lookup.iter().map(|txt| txt.to_string()).collect())
+    }
+
+    /// Look up the SRV records for `name` (e.g. `_service._proto.host`).
+    pub async fn lookup_srv(&self, name: &str) -> Result<Vec<SrvRecord>, BoxError> {
+        let resolver = self.shared_resolver().await?;
+        let lookup = resolver.srv_lookup(fully_qualify(name)).await?;
+        Ok(lookup.iter().map(SrvRecord::from).collect())
+    }
+
+    /// Resolve `host` via this resolver's configured SRV service, returning
+    /// the resolved targets' addresses with their SRV ports preserved,
+    /// ordered by priority then weighted-random within a priority group.
+    async fn resolve_srv(&self, service: &str, host: &str) -> Result<Addrs, BoxError> {
+        let resolver = self.shared_resolver().await?;
+        let srv_name = fully_qualify(&format!("{}.{}", service, host));
+        let lookup = resolver.srv_lookup(srv_name).await?;
+        let records: Vec<SrvRecord> = lookup.iter().map(SrvRecord::from).collect();
+
+        let mut socket_addrs = Vec::new();
+        for record in order_srv_records(records) {
+            let target_lookup = resolver.lookup_ip(fully_qualify(&record.target)).await?;
+            let ips: Vec<IpAddr> = target_lookup.into_iter().collect();
+            let ips = order_by_ip_version(ips, self.ip_version);
+            socket_addrs.extend(ips.into_iter().map(|ip| SocketAddr::new(ip, record.port)));
+        }
+
+        Ok(Box::new(SocketAddrs {
+            iter: socket_addrs.into_iter(),
+        }))
+    }
+
+    /// Create a new resolver that speaks DNS-over-TLS to the given
+    /// nameservers, using the default `ResolverOpts`.
+    ///
+    /// Each nameserver is paired with the TLS name it should be validated
+    /// against (e.g. `1.1.1.1` with `"cloudflare-dns.com"`).
+    pub fn with_dns_over_tls(nameservers: &[(IpAddr, String)]) -> Self {
+        let mut group = NameServerConfigGroup::with_capacity(nameservers.len());
+        for (ip, tls_dns_name) in nameservers {
+            group.merge(NameServerConfigGroup::from_ips_tls(
+                &[*ip],
+                853,
+                tls_dns_name.clone(),
+                true,
+            ));
+        }
+        let config = ResolverConfig::from_parts(None, vec![], group);
+        Self::with_config(config, ResolverOpts::default())
+    }
+
+    /// Create a new resolver that speaks DNS-over-HTTPS to the given
+    /// nameservers, using the default `ResolverOpts`.
+    ///
+    /// Each nameserver is paired with the TLS name it should be validated
+    /// against (e.g. `9.9.9.9` with `"dns.quad9.net"`).
+    pub fn with_dns_over_https(nameservers: &[(IpAddr, String)]) -> Self {
+        let mut group = NameServerConfigGroup::with_capacity(nameservers.len());
+        for (ip, tls_dns_name) in nameservers {
+            group.merge(NameServerConfigGroup::from_ips_https(
+                &[*ip],
+                443,
+                tls_dns_name.clone(),
+                true,
+            ));
+        }
+        let config = ResolverConfig::from_parts(None, vec![], group);
+        Self::with_config(config, ResolverOpts::default())
+    }
 }
 
 impl Resolve for TrustDnsResolver {
     fn resolve(&self, name: Name) -> Resolving {
-        let resolver = self.clone();
+        let this = self.clone();
         Box::pin(async move {
-            let mut lock = resolver.state.lock().await;
-
-            let resolver = match &*lock {
-                State::Init => {
-                    let resolver = new_resolver().await?;
-                    *lock = State::Ready(resolver.clone());
-                    resolver
-                }
-                State::Ready(resolver) => resolver.clone(),
-            };
+            if let Some(service) = this.srv.clone() {
+                return this.resolve_srv(&service, name.as_str()).await;
+            }
 
-            // Don't keep lock once the resolver is constructed, otherwise
-            // only one lookup could be done at a time.
-            drop(lock);
+            let ip_version = this.ip_version;
+            let resolver = this.shared_resolver().await?;
 
             let lookup = resolver.lookup_ip(name.as_str()).await?;
+            let ips: Vec<IpAddr> = lookup.into_iter().collect();
+            let ips = order_by_ip_version(ips, ip_version);
+            let socket_addrs: Vec<SocketAddr> =
+                ips.into_iter().map(|ip| SocketAddr::new(ip, 0)).collect();
             let addrs: Addrs = Box::new(SocketAddrs {
-                iter: lookup.into_iter(),
+                iter: socket_addrs.into_iter(),
             });
             Ok(addrs)
         })
@@ -104,18 +441,10 @@ impl Iterator for SocketAddrs {
     type Item = SocketAddr;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|ip_addr| SocketAddr::new(ip_addr, 0))
+        self.iter.next()
     }
 }
 
-async fn new_resolver() -> Result<SharedResolver, BoxError> {
-    let (config, opts) = get_system_conf()
-        .as_ref()
-        .expect("can't construct TrustDnsResolver if SYSTEM_CONF is error")
-        .clone();
-    new_resolver_with_config(config, opts)
-}
-
 fn new_resolver_with_config(
     config: ResolverConfig,
     opts: ResolverOpts,
@@ -123,3 +452,170 @@ fn new_resolver_with_config(
     let resolver = AsyncResolver::new(config, opts, TokioHandle)?;
     Ok(Arc::new(resolver))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trust_dns_resolver::config::Protocol;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn init_config(resolver: &TrustDnsResolver) -> ResolverConfig {
+        match &*resolver.state.try_lock().unwrap() {
+            State::Init(config, _) => config.clone(),
+            State::Ready(_) => panic!("resolver should not be built yet"),
+        }
+    }
+
+    fn srv(priority: u16, weight: u16) -> SrvRecord {
+        SrvRecord {
+            priority,
+            weight,
+            port: 0,
+            target: format!("{}-{}.example.com.", priority, weight),
+        }
+    }
+
+    #[test]
+    fn fully_qualify_appends_trailing_dot() {
+        assert_eq!(fully_qualify("example.com"), "example.com.");
+    }
+
+    #[test]
+    fn fully_qualify_is_idempotent() {
+        assert_eq!(fully_qualify("example.com."), "example.com.");
+    }
+
+    #[test]
+    fn order_by_ip_version_system_is_unchanged() {
+        let addrs = vec![ip("::1"), ip("127.0.0.1"), ip("::2")];
+        let ordered = order_by_ip_version(addrs.clone(), IpVersionPreference::System);
+        assert_eq!(ordered, addrs);
+    }
+
+    #[test]
+    fn order_by_ip_version_ipv4_first_prefers_v4() {
+        let addrs = vec![ip("::1"), ip("127.0.0.1"), ip("::2"), ip("127.0.0.2")];
+        let ordered = order_by_ip_version(addrs, IpVersionPreference::Ipv4First);
+        assert_eq!(
+            ordered,
+            vec![ip("127.0.0.1"), ip("127.0.0.2"), ip("::1"), ip("::2")]
+        );
+    }
+
+    #[test]
+    fn order_by_ip_version_ipv6_first_prefers_v6() {
+        let addrs = vec![ip("127.0.0.1"), ip("::1"), ip("127.0.0.2"), ip("::2")];
+        let ordered = order_by_ip_version(addrs, IpVersionPreference::Ipv6First);
+        assert_eq!(
+            ordered,
+            vec![ip("::1"), ip("::2"), ip("127.0.0.1"), ip("127.0.0.2")]
+        );
+    }
+
+    #[test]
+    fn order_by_ip_version_happy_interleaves_families() {
+        let addrs = vec![
+            ip("127.0.0.1"),
+            ip("::1"),
+            ip("127.0.0.2"),
+            ip("::2"),
+            ip("127.0.0.3"),
+        ];
+        let ordered = order_by_ip_version(addrs, IpVersionPreference::Happy);
+        assert_eq!(
+            ordered,
+            vec![
+                ip("::1"),
+                ip("127.0.0.1"),
+                ip("::2"),
+                ip("127.0.0.2"),
+                ip("127.0.0.3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn order_by_ip_version_happy_keeps_only_family() {
+        let addrs = vec![ip("127.0.0.1"), ip("127.0.0.2")];
+        let ordered = order_by_ip_version(addrs.clone(), IpVersionPreference::Happy);
+        assert_eq!(ordered, addrs);
+    }
+
+    #[test]
+    fn ip_version_preference_from_lookup_ip_strategy() {
+        assert_eq!(
+            IpVersionPreference::from(LookupIpStrategy::Ipv4Only),
+            IpVersionPreference::Ipv4First
+        );
+        assert_eq!(
+            IpVersionPreference::from(LookupIpStrategy::Ipv6Only),
+            IpVersionPreference::Ipv6First
+        );
+        assert_eq!(
+            IpVersionPreference::from(LookupIpStrategy::Ipv4AndIpv6),
+            IpVersionPreference::System
+        );
+    }
+
+    #[test]
+    fn order_srv_records_sorts_by_priority_ascending() {
+        let records = vec![srv(20, 0), srv(10, 0), srv(30, 0)];
+        let ordered = order_srv_records(records);
+        let priorities: Vec<u16> = ordered.iter().map(|record| record.priority).collect();
+        assert_eq!(priorities, vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn order_srv_records_keeps_priority_groups_together() {
+        let records = vec![srv(10, 5), srv(20, 1), srv(10, 1), srv(20, 5)];
+        let ordered = order_srv_records(records);
+        let priorities: Vec<u16> = ordered.iter().map(|record| record.priority).collect();
+        assert_eq!(priorities, vec![10, 10, 20, 20]);
+    }
+
+    #[test]
+    fn weighted_shuffle_keeps_all_records() {
+        let group = vec![srv(10, 0), srv(10, 5), srv(10, 100)];
+        let shuffled = weighted_shuffle(group.clone());
+        assert_eq!(shuffled.len(), group.len());
+        for record in &group {
+            assert!(shuffled.contains(record));
+        }
+    }
+
+    #[test]
+    fn weighted_shuffle_handles_zero_weights() {
+        let group = vec![srv(10, 0), srv(10, 0)];
+        let shuffled = weighted_shuffle(group.clone());
+        assert_eq!(shuffled.len(), 2);
+    }
+
+    #[test]
+    fn with_dns_over_tls_builds_tls_name_server() {
+        let nameservers = vec![(ip("1.1.1.1"), "cloudflare-dns.com".to_owned())];
+        let resolver = TrustDnsResolver::with_dns_over_tls(&nameservers);
+        let config = init_config(&resolver);
+
+        let server = &config.name_servers()[0];
+        assert_eq!(server.socket_addr.port(), 853);
+        assert_eq!(server.protocol, Protocol::Tls);
+        assert_eq!(server.tls_dns_name.as_deref(), Some("cloudflare-dns.com"));
+        assert!(server.trust_nx_responses);
+    }
+
+    #[test]
+    fn with_dns_over_https_builds_https_name_server() {
+        let nameservers = vec![(ip("9.9.9.9"), "dns.quad9.net".to_owned())];
+        let resolver = TrustDnsResolver::with_dns_over_https(&nameservers);
+        let config = init_config(&resolver);
+
+        let server = &config.name_servers()[0];
+        assert_eq!(server.socket_addr.port(), 443);
+        assert_eq!(server.protocol, Protocol::Https);
+        assert_eq!(server.tls_dns_name.as_deref(), Some("dns.quad9.net"));
+        assert!(server.trust_nx_responses);
+    }
+}